@@ -648,6 +648,10 @@ pub fn set(&self, features: &mut Features, span: Span) {
     /// Allows `extern "wasm" fn`
     (active, wasm_abi, "1.53.0", Some(83788), None),
 
+    /// Allows the attributes which guide the Yk meta-tracer, e.g. `#[yk_trace_budget]` and
+    /// `#[yk_stable_address]`.
+    (active, yk, "1.53.0", None, None),
+
     // -------------------------------------------------------------------------
     // feature-group-end: actual feature gates
     // -------------------------------------------------------------------------
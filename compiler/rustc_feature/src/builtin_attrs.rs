@@ -95,6 +95,7 @@ pub struct AttributeTemplate {
     pub word: bool,
     pub list: Option<&'static str>,
     pub name_value_str: Option<&'static str>,
+    pub name_value_int: Option<&'static str>,
 }
 
 /// A convenience macro for constructing attribute templates.
@@ -104,6 +105,9 @@ macro_rules! template {
     (Word) => { template!(@ true, None, None) };
     (List: $descr: expr) => { template!(@ false, Some($descr), None) };
     (NameValueStr: $descr: expr) => { template!(@ false, None, Some($descr)) };
+    (NameValueInt: $descr: expr) => { AttributeTemplate {
+        name_value_int: Some($descr), ..template!(@ false, None, None)
+    } };
     (Word, List: $descr: expr) => { template!(@ true, Some($descr), None) };
     (Word, NameValueStr: $descr: expr) => { template!(@ true, None, Some($descr)) };
     (List: $descr1: expr, NameValueStr: $descr2: expr) => {
@@ -113,7 +117,7 @@ macro_rules! template {
         template!(@ true, Some($descr1), Some($descr2))
     };
     (@ $word: expr, $list: expr, $name_value_str: expr) => { AttributeTemplate {
-        word: $word, list: $list, name_value_str: $name_value_str
+        word: $word, list: $list, name_value_str: $name_value_str, name_value_int: None
     } };
 }
 
@@ -345,6 +349,13 @@ macro_rules! experimental {
 
     gated!(cmse_nonsecure_entry, AssumedUsed, template!(Word), experimental!(cmse_nonsecure_entry)),
 
+    // Yk meta-tracing:
+    gated!(yk_stable_address, AssumedUsed, template!(Word), yk, experimental!(yk_stable_address)),
+    gated!(
+        yk_trace_budget, AssumedUsed, template!(NameValueInt: "N"), yk,
+        experimental!(yk_trace_budget),
+    ),
+
     // ==========================================================================
     // Internal attributes: Stability, deprecation, and unsafe:
     // ==========================================================================
@@ -577,6 +588,8 @@ macro_rules! experimental {
     rustc_attr!(TEST, rustc_symbol_name, AssumedUsed, template!(Word)),
     rustc_attr!(TEST, rustc_polymorphize_error, AssumedUsed, template!(Word)),
     rustc_attr!(TEST, rustc_def_path, AssumedUsed, template!(Word)),
+    rustc_attr!(TEST, rustc_yk_unsafe_regions, AssumedUsed, template!(Word)),
+    rustc_attr!(TEST, rustc_yk_side_effects, AssumedUsed, template!(Word)),
     rustc_attr!(TEST, rustc_mir, AssumedUsed, template!(List: "arg1, arg2, ...")),
     rustc_attr!(TEST, rustc_dump_program_clauses, AssumedUsed, template!(Word)),
     rustc_attr!(TEST, rustc_dump_env_program_clauses, AssumedUsed, template!(Word)),
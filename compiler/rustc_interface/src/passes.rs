@@ -1015,6 +1015,10 @@ pub fn start_codegen<'tcx>(
 ) -> Box<dyn Any> {
     info!("Pre-codegen\n{:?}", tcx.debug_stats());
 
+    if tcx.sess.opts.debugging_opts.yk_print_mir_hash {
+        mir::transform::yk_mir_hash::print_mir_hashes(tcx);
+    }
+
     let (metadata, need_metadata_module) = encode_and_write_metadata(tcx, outputs);
 
     let codegen = tcx.sess.time("codegen_crate", move || {
@@ -1026,6 +1030,7 @@ pub fn start_codegen<'tcx>(
     if tcx.sess.opts.output_types.should_codegen() {
         rustc_incremental::assert_module_sources::assert_module_sources(tcx);
         rustc_symbol_mangling::test::report_symbol_names(tcx);
+        mir::transform::yk_test::report_yk_queries(tcx);
     }
 
     info!("Post-codegen\n{:?}", tcx.debug_stats());
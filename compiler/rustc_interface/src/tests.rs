@@ -528,6 +528,7 @@ macro_rules! untracked {
     untracked!(unstable_options, true);
     untracked!(validate_mir, true);
     untracked!(verbose, true);
+    untracked!(yk_print_mir_hash, true);
 
     macro_rules! tracked {
         ($name: ident, $non_default_value: expr) => {
@@ -601,6 +602,9 @@ macro_rules! tracked {
     tracked!(use_ctors_section, Some(true));
     tracked!(verify_llvm_ir, true);
     tracked!(wasi_exec_model, Some(WasiExecModel::Reactor));
+    tracked!(yk_canonicalise, true);
+    tracked!(yk_hoist_invariant, true);
+    tracked!(yk_split_critical_edges, true);
 }
 
 #[test]
@@ -41,6 +41,9 @@ pub struct CodegenFnAttrs {
     /// The `#[repr(align(...))]` attribute. Indicates the value of which the function should be
     /// aligned to.
     pub alignment: Option<u32>,
+    /// The `#[yk_trace_budget = N]` attribute. The maximum number of SIR statements that the
+    /// Yk JIT should be willing to compile for this function.
+    pub yk_trace_budget: Option<u32>,
 }
 
 bitflags! {
@@ -107,6 +110,7 @@ pub fn new() -> CodegenFnAttrs {
             no_sanitize: SanitizerSet::empty(),
             instruction_set: None,
             alignment: None,
+            yk_trace_budget: None,
         }
     }
 
@@ -15,7 +15,7 @@
 use std::cell::Cell;
 use std::fmt::{self, Debug};
 
-use super::{Field, SourceInfo};
+use super::{BasicBlock, Field, SourceInfo};
 
 #[derive(Copy, Clone, PartialEq, TyEncodable, TyDecodable, HashStable, Debug)]
 pub enum UnsafetyViolationKind {
@@ -401,6 +401,44 @@ pub struct CoverageInfo {
     pub num_expressions: u32,
 }
 
+/// A straight-line run of basic blocks from `first_block` to `last_block`, each of which contains
+/// an unsafe operation of the same kind. Every block after the first is the sole successor of the
+/// one before it, and has that block as its sole predecessor. These are returned by the
+/// `yk_trace_unsafe_regions` query, so that the JIT can guard around, or refuse to trace, them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, HashStable)]
+pub struct UnsafeRegion {
+    pub first_block: BasicBlock,
+    pub last_block: BasicBlock,
+    pub kind: UnsafeKind,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, HashStable)]
+pub enum UnsafeKind {
+    /// A dereference of a raw pointer, either by a place projection or by a memory intrinsic.
+    RawPtrDeref,
+    /// A read from a union field.
+    UnionFieldAccess,
+    /// A call to a foreign function.
+    ExternCall,
+    /// Code inside an `unsafe` block or the body of an `unsafe fn`.
+    UnsafeBlock,
+}
+
+/// The side effects which a function's own body may have, as returned by the
+/// `yk_trace_side_effects` query. If such a function is re-executed after deoptimisation, its
+/// effects happen twice.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, HashStable)]
+pub enum YkSideEffectStatus {
+    /// The body neither calls foreign functions nor writes through raw pointers.
+    Pure,
+    /// The body calls a foreign function, which may perform I/O.
+    HasIO,
+    /// The body writes through a raw pointer, e.g. to a `static mut`.
+    HasGlobalMutation,
+    /// The function has no MIR to analyse.
+    Unknown,
+}
+
 /// Shims which make dealing with `WithOptConstParam` easier.
 ///
 /// For more information on why this is needed, consider looking
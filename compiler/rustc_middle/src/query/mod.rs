@@ -353,6 +353,31 @@
         cache_on_disk_if { key.is_local() }
     }
 
+    /// Returns the blocks of a function's optimized MIR that are the target of a back-edge.
+    query yk_loop_headers(key: LocalDefId) -> BitSet<mir::BasicBlock> {
+        desc { |tcx| "computing the loop headers of `{}`", tcx.def_path_str(key.to_def_id()) }
+        storage(ArenaCacheSelector<'tcx>)
+    }
+
+    /// Returns a hash of a function's optimized MIR which ignores spans, and so only changes when
+    /// the function's behaviour may have changed. Stable across compilation sessions.
+    query yk_mir_hash(key: LocalDefId) -> u64 {
+        desc { |tcx| "hashing the optimized MIR of `{}`", tcx.def_path_str(key.to_def_id()) }
+    }
+
+    /// Returns the regions of a function's optimized MIR which perform unsafe operations, so that
+    /// the JIT can guard around them or decline to trace them.
+    query yk_trace_unsafe_regions(key: LocalDefId) -> Vec<mir::UnsafeRegion> {
+        desc { |tcx| "finding the unsafe regions of `{}`", tcx.def_path_str(key.to_def_id()) }
+        storage(ArenaCacheSelector<'tcx>)
+    }
+
+    /// Returns which side effects, if any, a function's own body may have. Callees are not taken
+    /// into account.
+    query yk_trace_side_effects(key: DefId) -> mir::YkSideEffectStatus {
+        desc { |tcx| "finding the side effects of `{}`", tcx.def_path_str(key) }
+    }
+
     /// The `DefId` is the `DefId` of the containing MIR body. Promoteds do not have their own
     /// `DefId`. This function returns all promoteds in the specified body. The body references
     /// promoteds by the `DefId` and the `mir::Promoted` index. This is necessary, because
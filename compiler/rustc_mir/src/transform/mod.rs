@@ -54,12 +54,26 @@
 pub mod uninhabited_enum_branching;
 pub mod unreachable_prop;
 pub mod validate;
+pub mod yk_canonicalise;
+pub mod yk_hoist_invariant;
+pub mod yk_loop_headers;
+pub mod yk_mir_hash;
+#[cfg(test)]
+mod yk_mock_cfg;
+pub mod yk_side_effects;
+pub mod yk_split_critical_edges;
+pub mod yk_test;
+pub mod yk_unsafe_regions;
 
 pub use rustc_middle::mir::MirSource;
 
 pub(crate) fn provide(providers: &mut Providers) {
     self::check_unsafety::provide(providers);
     self::check_packed_ref::provide(providers);
+    self::yk_loop_headers::provide(providers);
+    self::yk_mir_hash::provide(providers);
+    self::yk_side_effects::provide(providers);
+    self::yk_unsafe_regions::provide(providers);
     *providers = Providers {
         mir_keys,
         mir_const,
@@ -515,6 +529,8 @@ fn run_optimization_passes<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
         &simplify::SimplifyLocals,
         &multiple_return_terminators::MultipleReturnTerminators,
         &deduplicate_blocks::DeduplicateBlocks,
+        // Only does anything with `-Z yk-hoist-invariant`.
+        &yk_hoist_invariant::YkHoistInvariant,
     ];
 
     // Optimizations to run even if mir optimizations have been disabled.
@@ -526,6 +542,11 @@ fn run_optimization_passes<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
     // Some cleanup necessary at least for LLVM and potentially other codegen backends.
     let pre_codegen_cleanup: &[&dyn MirPass<'tcx>] = &[
         &add_call_guards::CriticalCallEdges,
+        // Only does anything with `-Z yk-canonicalise`.
+        &yk_canonicalise::YkMirCanonicaliser,
+        // Only does anything with `-Z yk-split-critical-edges`. SSA construction for SIR would
+        // run directly after it.
+        &yk_split_critical_edges::YkSplitCriticalEdges,
         // Dump the end result for testing and debugging purposes.
         &dump_mir::Marker("PreCodegen"),
     ];
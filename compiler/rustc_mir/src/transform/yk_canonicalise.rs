@@ -0,0 +1,167 @@
+//! Puts MIR into a canonical form for SIR emission.
+//!
+//! Different optimisation paths can produce MIR for the same function which differs only in
+//! incidental ways, e.g. in the numbering of its locals. Such differences change the function's
+//! `yk_mir_hash`, needlessly invalidating JIT traces which pass through it. This pass removes the
+//! most common of them:
+//!
+//! 1. Storage markers, which are only emitted when optimising, are removed, as are `nop`s.
+//! 2. Locals other than the return place and arguments are renumbered in order of their first
+//!    occurrence in a reverse post-order traversal of the CFG.
+//! 3. The targets of each `SwitchInt` are sorted by value.
+//! 4. The operands of commutative binary operations are ordered so that places come before
+//!    constants, and places with lower-numbered locals come first.
+//!
+//! It only runs when `-Z yk-canonicalise` is passed.
+
+use crate::transform::MirPass;
+use rustc_index::bit_set::BitSet;
+use rustc_index::vec::{Idx, IndexVec};
+use rustc_middle::mir::traversal;
+use rustc_middle::mir::visit::{MutVisitor, PlaceContext, Visitor};
+use rustc_middle::mir::*;
+use rustc_middle::ty::TyCtxt;
+
+pub struct YkMirCanonicaliser;
+
+impl<'tcx> MirPass<'tcx> for YkMirCanonicaliser {
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        if !tcx.sess.opts.debugging_opts.yk_canonicalise {
+            return;
+        }
+
+        debug!("YkMirCanonicaliser: canonicalising {:?}", body.source);
+        remove_storage_markers_and_nops(body);
+        renumber_locals(tcx, body);
+        sort_switch_targets(body);
+        order_commutative_operands(body);
+    }
+}
+
+fn remove_storage_markers_and_nops(body: &mut Body<'_>) {
+    for data in body.basic_blocks_mut() {
+        data.statements.retain(|stmt| {
+            !matches!(
+                stmt.kind,
+                StatementKind::StorageLive(..)
+                    | StatementKind::StorageDead(..)
+                    | StatementKind::Nop
+            )
+        });
+    }
+}
+
+fn renumber_locals<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+    // The return place and arguments have fixed positions, so they keep their numbers.
+    let mut order = FirstOccurrences {
+        order: Vec::with_capacity(body.local_decls.len()),
+        seen: BitSet::new_empty(body.local_decls.len()),
+    };
+    for local in (0..=body.arg_count).map(Local::new) {
+        order.record(local);
+    }
+    for (bb, data) in traversal::reverse_postorder(body) {
+        order.visit_basic_block_data(bb, data);
+    }
+    // Locals which never occur in a reachable block (e.g. those only mentioned in debuginfo) go
+    // last, in their original order.
+    for local in body.local_decls.indices() {
+        order.record(local);
+    }
+    let order = order.order;
+
+    if order.iter().enumerate().all(|(new, old)| old.index() == new) {
+        return;
+    }
+
+    let mut map = IndexVec::from_elem(RETURN_PLACE, &body.local_decls);
+    for (new, &old) in order.iter().enumerate() {
+        map[old] = Local::new(new);
+    }
+    body.local_decls = order.iter().map(|&old| body.local_decls[old].clone()).collect();
+    LocalRenumberer { map, tcx }.visit_body(body);
+}
+
+/// Records each local in the order in which it is first seen.
+struct FirstOccurrences {
+    order: Vec<Local>,
+    seen: BitSet<Local>,
+}
+
+impl FirstOccurrences {
+    fn record(&mut self, local: Local) {
+        if self.seen.insert(local) {
+            self.order.push(local);
+        }
+    }
+}
+
+impl<'tcx> Visitor<'tcx> for FirstOccurrences {
+    fn visit_local(&mut self, &local: &Local, _: PlaceContext, _: Location) {
+        self.record(local);
+    }
+}
+
+struct LocalRenumberer<'tcx> {
+    map: IndexVec<Local, Local>,
+    tcx: TyCtxt<'tcx>,
+}
+
+impl<'tcx> MutVisitor<'tcx> for LocalRenumberer<'tcx> {
+    fn tcx(&self) -> TyCtxt<'tcx> {
+        self.tcx
+    }
+
+    fn visit_local(&mut self, local: &mut Local, _: PlaceContext, _: Location) {
+        *local = self.map[*local];
+    }
+}
+
+fn sort_switch_targets(body: &mut Body<'_>) {
+    for data in body.basic_blocks_mut() {
+        if let TerminatorKind::SwitchInt { targets, .. } = &mut data.terminator_mut().kind {
+            let otherwise = targets.otherwise();
+            let mut pairs: Vec<_> = targets.iter().collect();
+            pairs.sort_unstable_by_key(|&(value, _)| value);
+            *targets = SwitchTargets::new(pairs.into_iter(), otherwise);
+        }
+    }
+}
+
+fn order_commutative_operands(body: &mut Body<'_>) {
+    for data in body.basic_blocks_mut() {
+        for stmt in &mut data.statements {
+            if let StatementKind::Assign(box (
+                _,
+                Rvalue::BinaryOp(op, box (lhs, rhs)) | Rvalue::CheckedBinaryOp(op, box (lhs, rhs)),
+            )) = &mut stmt.kind
+            {
+                if is_commutative(*op) && operand_key(rhs) < operand_key(lhs) {
+                    std::mem::swap(lhs, rhs);
+                }
+            }
+        }
+    }
+}
+
+fn is_commutative(op: BinOp) -> bool {
+    matches!(
+        op,
+        BinOp::Add
+            | BinOp::Mul
+            | BinOp::BitXor
+            | BinOp::BitAnd
+            | BinOp::BitOr
+            | BinOp::Eq
+            | BinOp::Ne
+    )
+}
+
+/// The key by which the operands of a commutative operation are ordered. Operands with equal keys
+/// are left in their original order.
+fn operand_key(operand: &Operand<'_>) -> (u8, usize) {
+    match operand {
+        Operand::Copy(place) | Operand::Move(place) => (0, place.local.index()),
+        Operand::Constant(_) => (1, 0),
+    }
+}
@@ -0,0 +1,295 @@
+//! Hoists loop-invariant computations out of loops.
+//!
+//! Every statement inside a loop adds to the length of any trace recorded through it, so
+//! computations whose result is the same on each iteration are moved into the loop's pre-header,
+//! where they are executed (and traced) once. A statement `d = rvalue` is hoisted when:
+//!
+//! * `rvalue` is a copy, a constant, or a binary operation which cannot trap or cause undefined
+//!   behaviour, even when executed on a path where it originally was not;
+//! * `d` is a temporary or user variable of a `Copy` type which is assigned nowhere else and never
+//!   borrowed;
+//! * every operand is a constant, or a (field of a) local which is never borrowed and is not
+//!   assigned inside the loop, or which is the destination of an already-hoisted statement.
+//!
+//! Since `d` now holds its value for the remainder of the function, its storage markers are
+//! removed and every move out of it becomes a copy. Induction variables are never hoisted, as they
+//! are assigned inside the loop by definition.
+//!
+//! It only runs when `-Z yk-hoist-invariant` is passed.
+
+use crate::transform::dest_prop::ever_borrowed_locals;
+use crate::transform::yk_loop_headers::loop_headers;
+use crate::transform::MirPass;
+use rustc_data_structures::graph::dominators::Dominators;
+use rustc_index::bit_set::BitSet;
+use rustc_index::vec::{Idx, IndexVec};
+use rustc_middle::mir::visit::{MutVisitor, PlaceContext, Visitor};
+use rustc_middle::mir::*;
+use rustc_middle::ty::{ParamEnv, TyCtxt};
+use rustc_span::DUMMY_SP;
+
+pub struct YkHoistInvariant;
+
+impl<'tcx> MirPass<'tcx> for YkHoistInvariant {
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        if !tcx.sess.opts.debugging_opts.yk_hoist_invariant {
+            return;
+        }
+
+        debug!("YkHoistInvariant: hoisting from {:?}", body.source);
+
+        let param_env = tcx.param_env(body.source.def_id());
+
+        // Process inner loops first, so that a statement hoisted into an inner loop's pre-header
+        // can then be hoisted out of the enclosing loop too.
+        let dominators = body.dominators();
+        let mut loops: Vec<_> = loop_headers(body)
+            .iter()
+            .map(|header| (natural_loop(body, &dominators, header).count(), header))
+            .collect();
+        loops.sort_unstable();
+
+        for (_, header) in loops {
+            hoist_from_loop(tcx, param_env, body, header);
+        }
+    }
+}
+
+fn hoist_from_loop<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    param_env: ParamEnv<'tcx>,
+    body: &mut Body<'tcx>,
+    header: BasicBlock,
+) {
+    if header == START_BLOCK || body[header].is_cleanup {
+        return;
+    }
+
+    // Pre-headers inserted for earlier loops change the CFG, so the loop is recomputed here.
+    let dominators = body.dominators();
+    let blocks = natural_loop(body, &dominators, header);
+    let borrowed = ever_borrowed_locals(body);
+    let defs = definitions(body);
+
+    let is_invariant = |local: Local, hoisted: &BitSet<Local>| {
+        !borrowed.contains(local)
+            && (hoisted.contains(local) || defs[local].iter().all(|bb| !blocks.contains(*bb)))
+    };
+
+    // Only temporaries and user variables may be hoisted, not the return place or arguments. They
+    // must also be `Copy`: a hoisted constant of any other type would be moved out of on every
+    // iteration but assigned only once.
+    let arg_count = body.arg_count;
+    let mut is_copy = BitSet::new_empty(body.local_decls.len());
+    for (local, decl) in body.local_decls.iter_enumerated() {
+        if decl.ty.is_copy_modulo_regions(tcx.at(DUMMY_SP), param_env) {
+            is_copy.insert(local);
+        }
+    }
+
+    let mut hoisted = BitSet::new_empty(body.local_decls.len());
+    let mut statements = Vec::new();
+    loop {
+        let mut changed = false;
+        for bb in blocks.iter() {
+            if body[bb].is_cleanup {
+                continue;
+            }
+            for stmt in body.basic_blocks_mut()[bb].statements.iter_mut() {
+                let dest = match hoistable_assignment(stmt) {
+                    Some((dest, operands)) => {
+                        let operands_invariant = operands.iter().all(|operand| match operand {
+                            Operand::Constant(_) => true,
+                            Operand::Copy(place) | Operand::Move(place) => {
+                                is_invariant(place.local, &hoisted)
+                            }
+                        });
+                        if !operands_invariant {
+                            continue;
+                        }
+                        dest
+                    }
+                    None => continue,
+                };
+                if hoisted.contains(dest)
+                    || defs[dest].len() != 1
+                    || borrowed.contains(dest)
+                    || dest.index() <= arg_count
+                    || !is_copy.contains(dest)
+                {
+                    continue;
+                }
+
+                debug!("YkHoistInvariant: hoisting {:?} out of the loop at {:?}", stmt, header);
+                hoisted.insert(dest);
+                statements.push(stmt.clone());
+                stmt.make_nop();
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    if statements.is_empty() {
+        return;
+    }
+
+    for data in body.basic_blocks_mut() {
+        for stmt in &mut data.statements {
+            if let StatementKind::StorageLive(local) | StatementKind::StorageDead(local) = stmt.kind
+            {
+                if hoisted.contains(local) {
+                    stmt.make_nop();
+                }
+            }
+        }
+    }
+
+    // Codegen may pass a large operand which is moved into a call by reference, letting the callee
+    // overwrite it, so a hoisted local must be copied on every iteration instead.
+    MoveToCopy { tcx, hoisted: &hoisted }.visit_body(body);
+
+    let pre_header = pre_header(body, header, &blocks);
+    body[pre_header].statements.extend(statements);
+}
+
+/// If `stmt` assigns a local from an rvalue which is safe to execute speculatively, returns the
+/// local and the rvalue's operands.
+fn hoistable_assignment<'a, 'tcx>(
+    stmt: &'a Statement<'tcx>,
+) -> Option<(Local, Vec<&'a Operand<'tcx>>)> {
+    let (place, rvalue) = match &stmt.kind {
+        StatementKind::Assign(box (place, rvalue)) => (place, rvalue),
+        _ => return None,
+    };
+    let dest = place.as_local()?;
+    let operands = match rvalue {
+        // Moving out of a local inside a loop is only valid if the move happens at most once, so
+        // hoisting it could leave the moved-from local's drop elaboration out of step.
+        Rvalue::Use(operand @ (Operand::Copy(_) | Operand::Constant(_))) => vec![operand],
+        Rvalue::BinaryOp(op, box (lhs, rhs))
+            if !matches!(op, BinOp::Div | BinOp::Rem | BinOp::Shl | BinOp::Shr | BinOp::Offset) =>
+        {
+            vec![lhs, rhs]
+        }
+        Rvalue::CheckedBinaryOp(BinOp::Add | BinOp::Sub | BinOp::Mul, box (lhs, rhs)) => {
+            vec![lhs, rhs]
+        }
+        _ => return None,
+    };
+    // Only field projections are allowed: they read from the local itself, not through a
+    // pointer or at a dynamic index.
+    let direct = operands.iter().all(|operand| match operand {
+        Operand::Constant(_) => true,
+        Operand::Copy(place) | Operand::Move(place) => {
+            place.projection.iter().all(|elem| matches!(elem, ProjectionElem::Field(..)))
+        }
+    });
+    if direct {
+        Some((dest, operands))
+    } else {
+        None
+    }
+}
+
+/// Returns the blocks of the natural loop headed by `header`: the header itself, and every block
+/// which can reach one of its back-edges without passing through it.
+fn natural_loop(
+    body: &Body<'_>,
+    dominators: &Dominators<BasicBlock>,
+    header: BasicBlock,
+) -> BitSet<BasicBlock> {
+    let mut blocks = BitSet::new_empty(body.basic_blocks().len());
+    blocks.insert(header);
+
+    let predecessors = body.predecessors();
+    let mut worklist: Vec<_> = predecessors[header]
+        .iter()
+        .copied()
+        .filter(|&pred| dominators.is_reachable(pred) && dominators.is_dominated_by(pred, header))
+        .collect();
+    while let Some(bb) = worklist.pop() {
+        if blocks.insert(bb) {
+            worklist.extend(
+                predecessors[bb].iter().copied().filter(|&pred| dominators.is_reachable(pred)),
+            );
+        }
+    }
+    blocks
+}
+
+/// Returns the block which is the sole entry into the loop headed by `header`, inserting one if
+/// necessary.
+fn pre_header(body: &mut Body<'_>, header: BasicBlock, blocks: &BitSet<BasicBlock>) -> BasicBlock {
+    let mut outside: Vec<_> = body.predecessors()[header]
+        .iter()
+        .copied()
+        .filter(|&pred| !blocks.contains(pred))
+        .collect();
+    outside.dedup();
+
+    // An existing predecessor can only be reused if its terminator does nothing but jump: a call,
+    // for example, may assign to one of the hoisted statements' operands.
+    if let [pred] = outside[..] {
+        if let TerminatorKind::Goto { .. } = body[pred].terminator().kind {
+            return pred;
+        }
+    }
+
+    let source_info = body[header].terminator().source_info;
+    let pre_header = body.basic_blocks_mut().push(BasicBlockData {
+        statements: vec![],
+        is_cleanup: false,
+        terminator: Some(Terminator { source_info, kind: TerminatorKind::Goto { target: header } }),
+    });
+    for pred in outside {
+        for target in body[pred].terminator_mut().successors_mut() {
+            if *target == header {
+                *target = pre_header;
+            }
+        }
+    }
+    pre_header
+}
+
+/// Turns every move out of a hoisted local into a copy.
+struct MoveToCopy<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    hoisted: &'a BitSet<Local>,
+}
+
+impl<'tcx> MutVisitor<'tcx> for MoveToCopy<'_, 'tcx> {
+    fn tcx(&self) -> TyCtxt<'tcx> {
+        self.tcx
+    }
+
+    fn visit_operand(&mut self, operand: &mut Operand<'tcx>, location: Location) {
+        if let Operand::Move(place) = *operand {
+            if self.hoisted.contains(place.local) {
+                *operand = Operand::Copy(place);
+            }
+        }
+        self.super_operand(operand, location);
+    }
+}
+
+/// Returns, for each local, the blocks containing an assignment to (part of) it.
+fn definitions(body: &Body<'_>) -> IndexVec<Local, Vec<BasicBlock>> {
+    let mut collector = DefCollector { defs: IndexVec::from_elem(vec![], &body.local_decls) };
+    collector.visit_body(body);
+    collector.defs
+}
+
+struct DefCollector {
+    defs: IndexVec<Local, Vec<BasicBlock>>,
+}
+
+impl<'tcx> Visitor<'tcx> for DefCollector {
+    fn visit_local(&mut self, &local: &Local, context: PlaceContext, location: Location) {
+        if context.is_mutating_use() {
+            self.defs[local].push(location.block);
+        }
+    }
+}
@@ -0,0 +1,42 @@
+//! Identifies the loop headers of a MIR body.
+//!
+//! A block `B` is a loop header if it is the target of a back-edge, i.e. if some predecessor `P`
+//! of `B` is dominated by `B`. Several Yk passes (hotness counting, trace region identification)
+//! need this information, so it is exposed both as a function over a `Body` -- for passes that are
+//! still transforming the body and so cannot query it -- and as the `yk_loop_headers` query over
+//! optimized MIR.
+
+use rustc_data_structures::graph::iterate::post_order_from;
+use rustc_hir::def_id::LocalDefId;
+use rustc_index::bit_set::BitSet;
+use rustc_middle::mir::{BasicBlock, Body, START_BLOCK};
+use rustc_middle::ty::query::Providers;
+use rustc_middle::ty::TyCtxt;
+
+#[cfg(test)]
+mod tests;
+
+pub(crate) fn provide(providers: &mut Providers) {
+    *providers = Providers { yk_loop_headers, ..*providers };
+}
+
+fn yk_loop_headers(tcx: TyCtxt<'_>, def_id: LocalDefId) -> BitSet<BasicBlock> {
+    loop_headers(tcx.optimized_mir(def_id))
+}
+
+/// Returns the set of blocks in `body` that are the target of at least one back-edge.
+///
+/// Only blocks reachable from `START_BLOCK` are considered, as the dominator tree says nothing
+/// about unreachable blocks.
+pub fn loop_headers(body: &Body<'_>) -> BitSet<BasicBlock> {
+    let dominators = body.dominators();
+    let mut headers = BitSet::new_empty(body.basic_blocks().len());
+    for bb in post_order_from(body, START_BLOCK) {
+        for &succ in body[bb].terminator().successors() {
+            if dominators.is_dominated_by(bb, succ) {
+                headers.insert(succ);
+            }
+        }
+    }
+    headers
+}
@@ -0,0 +1,32 @@
+use super::loop_headers;
+use crate::transform::yk_mock_cfg::{block, branch, goto};
+
+use rustc_index::vec::IndexVec;
+use rustc_middle::mir::*;
+
+#[test]
+fn nested_loops_have_two_headers() {
+    // bb0 -> bb1 (outer header) -> bb2 (inner header) -> bb3 -> bb2
+    //                                                  \-> bb4 -> bb1
+    //                           \-> bb5 (return)
+    let blocks: IndexVec<BasicBlock, _> =
+        vec![goto(1), branch(2, 5), branch(3, 4), goto(2), goto(1), block(TerminatorKind::Return)]
+            .into_iter()
+            .collect();
+    let body = Body::new_cfg_only(blocks);
+
+    let headers = loop_headers(&body);
+    assert_eq!(
+        headers.iter().collect::<Vec<_>>(),
+        vec![BasicBlock::from_usize(1), BasicBlock::from_usize(2)]
+    );
+}
+
+#[test]
+fn straight_line_has_no_headers() {
+    let blocks: IndexVec<BasicBlock, _> =
+        vec![goto(1), goto(2), block(TerminatorKind::Return)].into_iter().collect();
+    let body = Body::new_cfg_only(blocks);
+
+    assert!(loop_headers(&body).is_empty());
+}
@@ -0,0 +1,44 @@
+//! Computes a content hash of a function's optimized MIR.
+//!
+//! The Yk JIT caches compiled traces across runs of a program, and must discard a cached trace if
+//! any function it passes through has changed since the trace was compiled. The hash therefore
+//! covers everything that affects a function's behaviour -- the blocks and local declarations of
+//! its body and of its promoted constants -- but not its spans, so that (e.g.) adding a comment
+//! above a function does not invalidate traces.
+//!
+//! `-Z yk-print-mir-hash` prints the hash of every function in the crate, for testing.
+
+use rustc_data_structures::stable_hasher::{HashStable, StableHasher};
+use rustc_hir::def::DefKind;
+use rustc_hir::def_id::LocalDefId;
+use rustc_middle::ty::query::Providers;
+use rustc_middle::ty::TyCtxt;
+use std::iter;
+
+pub(crate) fn provide(providers: &mut Providers) {
+    *providers = Providers { yk_mir_hash, ..*providers };
+}
+
+fn yk_mir_hash(tcx: TyCtxt<'_>, def_id: LocalDefId) -> u64 {
+    let mut hasher = StableHasher::new();
+    let mut hcx = tcx.create_stable_hashing_context();
+    hcx.while_hashing_spans(false, |hcx| {
+        // Promoted constants are referred to only by their index, so their bodies must be hashed
+        // too.
+        let promoted = tcx.promoted_mir(def_id);
+        for body in iter::once(tcx.optimized_mir(def_id)).chain(promoted) {
+            body.basic_blocks().hash_stable(hcx, &mut hasher);
+            body.local_decls.hash_stable(hcx, &mut hasher);
+        }
+    });
+    hasher.finish()
+}
+
+/// Prints the `yk_mir_hash` of each function in the local crate, one per line.
+pub fn print_mir_hashes(tcx: TyCtxt<'_>) {
+    for def_id in tcx.body_owners() {
+        if let DefKind::Fn | DefKind::AssocFn | DefKind::Closure = tcx.def_kind(def_id) {
+            println!("{} {:016x}", tcx.def_path_str(def_id.to_def_id()), tcx.yk_mir_hash(def_id));
+        }
+    }
+}
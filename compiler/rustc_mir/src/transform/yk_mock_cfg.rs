@@ -0,0 +1,20 @@
+//! Builders for the blocks of mock, CFG-only bodies used in the unit tests of the Yk passes.
+
+use rustc_middle::mir::*;
+use rustc_span::DUMMY_SP;
+
+pub(super) fn block<'tcx>(kind: TerminatorKind<'tcx>) -> BasicBlockData<'tcx> {
+    BasicBlockData::new(Some(Terminator { source_info: SourceInfo::outermost(DUMMY_SP), kind }))
+}
+
+/// A block with two successors, which (unlike `SwitchInt`) needs no discriminant.
+pub(super) fn branch<'tcx>(a: usize, b: usize) -> BasicBlockData<'tcx> {
+    block(TerminatorKind::FalseEdge {
+        real_target: BasicBlock::from_usize(a),
+        imaginary_target: BasicBlock::from_usize(b),
+    })
+}
+
+pub(super) fn goto<'tcx>(target: usize) -> BasicBlockData<'tcx> {
+    block(TerminatorKind::Goto { target: BasicBlock::from_usize(target) })
+}
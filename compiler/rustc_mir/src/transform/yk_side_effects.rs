@@ -0,0 +1,86 @@
+//! Classifies the side effects of a function's own body.
+//!
+//! When a trace deoptimises, the interpreter resumes from the last safe point, and functions
+//! between that point and the failed guard are run again. That is harmless for functions without
+//! side effects, but would repeat any I/O or writes to global state, so the JIT needs stubs for
+//! such functions. Only the function's own body is examined: calls to other Rust functions are
+//! assumed to be pure, and it is up to the user of the query to check callees as well.
+
+use crate::transform::yk_unsafe_regions::{calls_foreign_fn, pointer_intrinsic_writes};
+use rustc_hir::def::DefKind;
+use rustc_hir::def_id::DefId;
+use rustc_middle::mir::visit::{PlaceContext, Visitor};
+use rustc_middle::mir::*;
+use rustc_middle::ty::query::Providers;
+use rustc_middle::ty::TyCtxt;
+
+pub(crate) fn provide(providers: &mut Providers) {
+    *providers = Providers { yk_trace_side_effects, ..*providers };
+}
+
+fn yk_trace_side_effects(tcx: TyCtxt<'_>, def_id: DefId) -> YkSideEffectStatus {
+    let has_body = matches!(
+        tcx.def_kind(def_id),
+        DefKind::Fn | DefKind::AssocFn | DefKind::Closure | DefKind::Generator
+    );
+    if !has_body || !tcx.is_mir_available(def_id) {
+        return YkSideEffectStatus::Unknown;
+    }
+
+    let body = tcx.optimized_mir(def_id);
+    let mut finder = SideEffectFinder { tcx, body, has_io: false, has_global_mutation: false };
+    finder.visit_body(body);
+
+    if finder.has_io {
+        YkSideEffectStatus::HasIO
+    } else if finder.has_global_mutation {
+        YkSideEffectStatus::HasGlobalMutation
+    } else {
+        YkSideEffectStatus::Pure
+    }
+}
+
+struct SideEffectFinder<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    body: &'a Body<'tcx>,
+    has_io: bool,
+    has_global_mutation: bool,
+}
+
+impl<'tcx> Visitor<'tcx> for SideEffectFinder<'_, 'tcx> {
+    fn visit_statement(&mut self, statement: &Statement<'tcx>, location: Location) {
+        // This is what `ptr::write` becomes once inlined: a copy into the destination pointer.
+        if let StatementKind::CopyNonOverlapping(..) = statement.kind {
+            self.has_global_mutation = true;
+        }
+
+        self.super_statement(statement, location);
+    }
+
+    fn visit_terminator(&mut self, terminator: &Terminator<'tcx>, location: Location) {
+        if let TerminatorKind::Call { func, .. } = &terminator.kind {
+            if calls_foreign_fn(self.tcx, self.body, func) {
+                self.has_io = true;
+            } else if pointer_intrinsic_writes(self.tcx, self.body, func) == Some(true) {
+                self.has_global_mutation = true;
+            }
+        }
+
+        self.super_terminator(terminator, location);
+    }
+
+    fn visit_place(&mut self, place: &Place<'tcx>, context: PlaceContext, location: Location) {
+        // A `static mut` is accessed through a raw pointer constant, so checking for writes
+        // through raw pointers covers both.
+        if context.is_mutating_use()
+            && place.iter_projections().any(|(base, elem)| {
+                matches!(elem, ProjectionElem::Deref)
+                    && base.ty(self.body, self.tcx).ty.is_unsafe_ptr()
+            })
+        {
+            self.has_global_mutation = true;
+        }
+
+        self.super_place(place, context, location);
+    }
+}
@@ -0,0 +1,89 @@
+//! Splits every critical edge in the CFG.
+//!
+//! A critical edge is one which is neither the only edge leaving its source block, nor the only
+//! edge entering its target block. φ-nodes for SIR's SSA form are placed at the start of join
+//! blocks, and their incoming values are materialised at the end of each predecessor. If a
+//! predecessor has several successors, that code would run on every path out of it, so each such
+//! edge is given a block of its own here.
+//!
+//! Unlike `AddCallGuards`, which only splits the edges following calls, this pass splits all of
+//! them. Edges into cleanup blocks are split with a cleanup block. Once split, an edge is no longer
+//! critical, so running the pass again leaves the body unchanged.
+//!
+//! It only runs when `-Z yk-split-critical-edges` is passed.
+
+use crate::transform::MirPass;
+use rustc_data_structures::fx::FxHashMap;
+use rustc_index::vec::{Idx, IndexVec};
+use rustc_middle::mir::*;
+use rustc_middle::ty::TyCtxt;
+
+#[cfg(test)]
+mod tests;
+
+pub struct YkSplitCriticalEdges;
+
+impl<'tcx> MirPass<'tcx> for YkSplitCriticalEdges {
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>) {
+        if !tcx.sess.opts.debugging_opts.yk_split_critical_edges {
+            return;
+        }
+
+        debug!("YkSplitCriticalEdges: splitting edges in {:?}", body.source);
+        split_critical_edges(body);
+    }
+}
+
+/// Splits the critical edges of `body`, returning the number of blocks inserted.
+pub fn split_critical_edges(body: &mut Body<'_>) -> usize {
+    // A block may jump to the same target along several edges (e.g. from a `SwitchInt` with
+    // duplicate targets). Those edges are all split together, so only distinct blocks count.
+    let pred_count: IndexVec<_, _> =
+        body.predecessors().iter().map(|preds| distinct(preds.iter().copied())).collect();
+    let is_cleanup: IndexVec<BasicBlock, _> =
+        body.basic_blocks().iter().map(|data| data.is_cleanup).collect();
+
+    let mut new_blocks = Vec::new();
+    let cur_len = body.basic_blocks().len();
+
+    for block in body.basic_blocks_mut() {
+        let terminator = block.terminator_mut();
+        if distinct(terminator.successors().copied()) < 2 {
+            continue;
+        }
+
+        let source_info = terminator.source_info;
+        let mut edge_blocks = FxHashMap::default();
+        for target in terminator.successors_mut() {
+            let join = *target;
+            if pred_count[join] < 2 {
+                continue;
+            }
+
+            *target = *edge_blocks.entry(join).or_insert_with(|| {
+                new_blocks.push(BasicBlockData {
+                    statements: vec![],
+                    is_cleanup: is_cleanup[join],
+                    terminator: Some(Terminator {
+                        source_info,
+                        kind: TerminatorKind::Goto { target: join },
+                    }),
+                });
+                BasicBlock::new(cur_len + new_blocks.len() - 1)
+            });
+        }
+    }
+
+    debug!("YkSplitCriticalEdges: inserted {} blocks", new_blocks.len());
+
+    let split = new_blocks.len();
+    body.basic_blocks_mut().extend(new_blocks);
+    split
+}
+
+fn distinct(blocks: impl Iterator<Item = BasicBlock>) -> usize {
+    let mut blocks: Vec<_> = blocks.collect();
+    blocks.sort_unstable();
+    blocks.dedup();
+    blocks.len()
+}
@@ -0,0 +1,83 @@
+use super::{distinct, split_critical_edges};
+use crate::transform::yk_mock_cfg::{block, branch, goto};
+
+use rustc_index::vec::IndexVec;
+use rustc_middle::mir::*;
+
+fn has_critical_edges(body: &Body<'_>) -> bool {
+    body.basic_blocks().iter().any(|data| {
+        let terminator = data.terminator();
+        distinct(terminator.successors().copied()) > 1
+            && terminator
+                .successors()
+                .any(|&succ| distinct(body.predecessors()[succ].iter().copied()) > 1)
+    })
+}
+
+#[test]
+fn three_predecessor_join() {
+    // bb0 -> bb1 -> bb2 -> bb3 (return)
+    //    \      \-------/
+    //     \-------------/
+    let blocks: IndexVec<BasicBlock, _> =
+        vec![branch(1, 3), branch(2, 3), goto(3), block(TerminatorKind::Return)]
+            .into_iter()
+            .collect();
+    let mut body = Body::new_cfg_only(blocks);
+    assert!(has_critical_edges(&body));
+
+    assert_eq!(split_critical_edges(&mut body), 2);
+    assert_eq!(body.basic_blocks().len(), 6);
+    assert!(!has_critical_edges(&body));
+
+    // The join block still has three predecessors, but each edge into it is now the only one
+    // leaving its source.
+    assert_eq!(body.predecessors()[BasicBlock::from_usize(3)].len(), 3);
+}
+
+#[test]
+fn idempotent() {
+    let blocks: IndexVec<BasicBlock, _> =
+        vec![branch(1, 3), branch(2, 3), goto(3), block(TerminatorKind::Return)]
+            .into_iter()
+            .collect();
+    let mut body = Body::new_cfg_only(blocks);
+
+    split_critical_edges(&mut body);
+    assert_eq!(split_critical_edges(&mut body), 0);
+    assert_eq!(body.basic_blocks().len(), 6);
+}
+
+#[test]
+fn duplicate_targets() {
+    // bb0 -> bb1 -> bb2 (return)
+    //    \      \==/
+    //     \--------/
+    //
+    // bb1 has two edges to bb2, but no other successor, so neither of them is critical.
+    let blocks: IndexVec<BasicBlock, _> =
+        vec![branch(1, 2), branch(2, 2), block(TerminatorKind::Return)].into_iter().collect();
+    let mut body = Body::new_cfg_only(blocks);
+    assert!(has_critical_edges(&body));
+
+    assert_eq!(split_critical_edges(&mut body), 1);
+    assert_eq!(body.basic_blocks().len(), 4);
+    assert!(!has_critical_edges(&body));
+    let bb1 = &body[BasicBlock::from_usize(1)];
+    assert!(bb1.terminator().successors().all(|&succ| succ == BasicBlock::from_usize(2)));
+}
+
+#[test]
+fn duplicate_edges_to_sole_successor() {
+    // bb0 -> bb1 (return)
+    //    \==/
+    //
+    // bb1 has a single predecessor, however many edges lead to it, so nothing is split.
+    let blocks: IndexVec<BasicBlock, _> =
+        vec![branch(1, 1), block(TerminatorKind::Return)].into_iter().collect();
+    let mut body = Body::new_cfg_only(blocks);
+    assert!(!has_critical_edges(&body));
+
+    assert_eq!(split_critical_edges(&mut body), 0);
+    assert_eq!(body.basic_blocks().len(), 2);
+}
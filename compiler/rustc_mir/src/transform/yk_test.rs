@@ -0,0 +1,72 @@
+//! Walks the crate looking for items with a `rustc_yk_unsafe_regions` or `rustc_yk_side_effects`
+//! attribute, and emits an error giving the result of, respectively, the `yk_trace_unsafe_regions`
+//! or `yk_trace_side_effects` query for each. This is used for unit testing the Yk queries, whose
+//! results are otherwise not visible in the compiler's output.
+
+use rustc_hir as hir;
+use rustc_hir::def_id::LocalDefId;
+use rustc_middle::ty::TyCtxt;
+use rustc_span::symbol::sym;
+
+pub fn report_yk_queries(tcx: TyCtxt<'_>) {
+    // If the `rustc_attrs` feature is not enabled, then the attributes we are interested in
+    // cannot be present anyway, so skip the walk.
+    if !tcx.features().rustc_attrs {
+        return;
+    }
+
+    tcx.dep_graph.with_ignore(|| {
+        let mut visitor = YkQueriesTest { tcx };
+        tcx.hir().krate().visit_all_item_likes(&mut visitor);
+    })
+}
+
+struct YkQueriesTest<'tcx> {
+    tcx: TyCtxt<'tcx>,
+}
+
+impl YkQueriesTest<'tcx> {
+    fn process_attrs(&mut self, def_id: LocalDefId) {
+        let tcx = self.tcx;
+        for attr in tcx.get_attrs(def_id.to_def_id()).iter() {
+            if tcx.sess.check_name(attr, sym::rustc_yk_unsafe_regions) {
+                let regions: Vec<_> = tcx
+                    .yk_trace_unsafe_regions(def_id)
+                    .iter()
+                    .map(|region| {
+                        if region.first_block == region.last_block {
+                            format!("{:?}: {:?}", region.first_block, region.kind)
+                        } else {
+                            format!(
+                                "{:?}..={:?}: {:?}",
+                                region.first_block, region.last_block, region.kind
+                            )
+                        }
+                    })
+                    .collect();
+                tcx.sess.span_err(attr.span, &format!("yk-unsafe-regions({})", regions.join(", ")));
+            } else if tcx.sess.check_name(attr, sym::rustc_yk_side_effects) {
+                let status = tcx.yk_trace_side_effects(def_id.to_def_id());
+                tcx.sess.span_err(attr.span, &format!("yk-side-effects({:?})", status));
+            }
+        }
+    }
+}
+
+impl hir::itemlikevisit::ItemLikeVisitor<'tcx> for YkQueriesTest<'tcx> {
+    fn visit_item(&mut self, item: &'tcx hir::Item<'tcx>) {
+        self.process_attrs(item.def_id);
+    }
+
+    fn visit_trait_item(&mut self, trait_item: &'tcx hir::TraitItem<'tcx>) {
+        self.process_attrs(trait_item.def_id);
+    }
+
+    fn visit_impl_item(&mut self, impl_item: &'tcx hir::ImplItem<'tcx>) {
+        self.process_attrs(impl_item.def_id);
+    }
+
+    fn visit_foreign_item(&mut self, foreign_item: &'tcx hir::ForeignItem<'tcx>) {
+        self.process_attrs(foreign_item.def_id);
+    }
+}
@@ -0,0 +1,221 @@
+//! Finds the parts of a function's optimized MIR which perform unsafe operations.
+//!
+//! Tracing over unsafe code needs care: the JIT cannot assume, for example, that a raw pointer
+//! still points to the same object when the trace is next run. The `yk_trace_unsafe_regions`
+//! query reports each block containing a raw pointer dereference (including one made by a memory
+//! intrinsic such as `copy_nonoverlapping`), a union field read, a call to a foreign function, or
+//! code from an `unsafe` block or `unsafe fn`. Blocks with the same kind of unsafety are merged
+//! into a single region when control can only flow straight from one into the next: each is the
+//! sole successor of the block before it, and has that block as its sole predecessor.
+
+use rustc_hir::def_id::LocalDefId;
+use rustc_index::bit_set::BitSet;
+use rustc_middle::mir::visit::{PlaceContext, Visitor};
+use rustc_middle::mir::*;
+use rustc_middle::ty::query::Providers;
+use rustc_middle::ty::{self, TyCtxt};
+use rustc_span::symbol::sym;
+use rustc_target::spec::abi::Abi;
+
+pub(crate) fn provide(providers: &mut Providers) {
+    *providers = Providers { yk_trace_unsafe_regions, ..*providers };
+}
+
+const KINDS: [UnsafeKind; 4] = [
+    UnsafeKind::RawPtrDeref,
+    UnsafeKind::UnionFieldAccess,
+    UnsafeKind::ExternCall,
+    UnsafeKind::UnsafeBlock,
+];
+
+fn yk_trace_unsafe_regions(tcx: TyCtxt<'_>, def_id: LocalDefId) -> Vec<UnsafeRegion> {
+    let body = tcx.optimized_mir(def_id);
+    let mut finder = UnsafeFinder {
+        tcx,
+        body,
+        blocks: vec![BitSet::new_empty(body.basic_blocks().len()); KINDS.len()],
+    };
+    finder.visit_body(body);
+
+    let predecessors = body.predecessors();
+    let continues = |blocks: &BitSet<BasicBlock>, pred: BasicBlock, bb: BasicBlock| {
+        blocks.contains(pred)
+            && blocks.contains(bb)
+            && predecessors[bb][..] == [pred]
+            && sole_successor(body, pred) == Some(bb)
+    };
+
+    let mut regions = Vec::new();
+    for (&kind, blocks) in KINDS.iter().zip(&finder.blocks) {
+        // Regions start at the blocks which do not continue one from their predecessor. Any blocks
+        // left over after those lie on a cycle, which is entered at its lowest numbered block.
+        let starts: Vec<_> = blocks
+            .iter()
+            .filter(|&bb| !matches!(predecessors[bb][..], [pred] if continues(blocks, pred, bb)))
+            .chain(blocks.iter())
+            .collect();
+        let mut covered = BitSet::new_empty(body.basic_blocks().len());
+        for first_block in starts {
+            if !covered.insert(first_block) {
+                continue;
+            }
+            let mut last_block = first_block;
+            while let Some(next) = sole_successor(body, last_block) {
+                if covered.contains(next) || !continues(blocks, last_block, next) {
+                    break;
+                }
+                covered.insert(next);
+                last_block = next;
+            }
+            regions.push(UnsafeRegion { first_block, last_block, kind });
+        }
+    }
+    regions.sort_by_key(|region| (region.first_block, region.kind));
+    regions
+}
+
+/// Returns the only block which `bb`'s terminator can jump to, if there is exactly one.
+fn sole_successor(body: &Body<'_>, bb: BasicBlock) -> Option<BasicBlock> {
+    let mut successors = body[bb].terminator().successors();
+    match (successors.next(), successors.next()) {
+        (Some(&succ), None) => Some(succ),
+        _ => None,
+    }
+}
+
+/// Returns whether `func`, the callee of a `Call` terminator in `body`, is a foreign function.
+pub(crate) fn calls_foreign_fn<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    body: &Body<'tcx>,
+    func: &Operand<'tcx>,
+) -> bool {
+    match *func.ty(body, tcx).kind() {
+        ty::FnDef(def_id, _) => {
+            // Intrinsics are declared in `extern` blocks, but are not foreign code.
+            tcx.is_foreign_item(def_id)
+                && !matches!(tcx.fn_sig(def_id).abi(), Abi::RustIntrinsic | Abi::PlatformIntrinsic)
+        }
+        _ => false,
+    }
+}
+
+/// If `func`, the callee of a `Call` terminator in `body`, is an intrinsic which accesses memory
+/// through a pointer argument, returns whether it writes through that pointer.
+pub(crate) fn pointer_intrinsic_writes<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    body: &Body<'tcx>,
+    func: &Operand<'tcx>,
+) -> Option<bool> {
+    let def_id = match *func.ty(body, tcx).kind() {
+        ty::FnDef(def_id, _)
+            if matches!(tcx.fn_sig(def_id).abi(), Abi::RustIntrinsic | Abi::PlatformIntrinsic) =>
+        {
+            def_id
+        }
+        _ => return None,
+    };
+    match tcx.item_name(def_id) {
+        sym::copy
+        | sym::copy_nonoverlapping
+        | sym::write_bytes
+        | sym::volatile_store
+        | sym::unaligned_volatile_store
+        | sym::volatile_set_memory
+        | sym::volatile_copy_memory
+        | sym::volatile_copy_nonoverlapping_memory
+        | sym::nontemporal_store => Some(true),
+        sym::volatile_load | sym::unaligned_volatile_load => Some(false),
+        name => {
+            let name = name.as_str();
+            if name.starts_with("atomic_load") {
+                Some(false)
+            } else if name.starts_with("atomic_fence")
+                || name.starts_with("atomic_singlethreadfence")
+            {
+                None
+            } else if name.starts_with("atomic_") {
+                // Stores, exchanges and read-modify-write operations.
+                Some(true)
+            } else {
+                None
+            }
+        }
+    }
+}
+
+struct UnsafeFinder<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    body: &'a Body<'tcx>,
+    /// For each of `KINDS`, the blocks containing that kind of unsafe operation.
+    blocks: Vec<BitSet<BasicBlock>>,
+}
+
+impl UnsafeFinder<'_, '_> {
+    fn record(&mut self, kind: UnsafeKind, location: Location) {
+        let index = KINDS.iter().position(|&k| k == kind).unwrap();
+        self.blocks[index].insert(location.block);
+    }
+
+    fn check_scope(&mut self, source_info: SourceInfo, location: Location) {
+        // Scopes inlined from other crates carry no safety information.
+        let body = self.body;
+        if let ClearCrossCrate::Set(data) = &body.source_scopes[source_info.scope].local_data {
+            if let Safety::ExplicitUnsafe(_) | Safety::FnUnsafe = data.safety {
+                self.record(UnsafeKind::UnsafeBlock, location);
+            }
+        }
+    }
+}
+
+impl<'tcx> Visitor<'tcx> for UnsafeFinder<'_, 'tcx> {
+    fn visit_statement(&mut self, statement: &Statement<'tcx>, location: Location) {
+        self.check_scope(statement.source_info, location);
+
+        // The source and destination are raw pointers, which are dereferenced by the copy itself.
+        if let StatementKind::CopyNonOverlapping(..) = statement.kind {
+            self.record(UnsafeKind::RawPtrDeref, location);
+        }
+
+        self.super_statement(statement, location);
+    }
+
+    fn visit_terminator(&mut self, terminator: &Terminator<'tcx>, location: Location) {
+        self.check_scope(terminator.source_info, location);
+
+        if let TerminatorKind::Call { func, .. } = &terminator.kind {
+            if calls_foreign_fn(self.tcx, self.body, func) {
+                self.record(UnsafeKind::ExternCall, location);
+            } else if pointer_intrinsic_writes(self.tcx, self.body, func).is_some() {
+                self.record(UnsafeKind::RawPtrDeref, location);
+            }
+        }
+
+        self.super_terminator(terminator, location);
+    }
+
+    fn visit_place(&mut self, place: &Place<'tcx>, context: PlaceContext, location: Location) {
+        // Debuginfo only describes where a variable lives; it performs no operation.
+        if !context.is_use() {
+            return;
+        }
+
+        let last = place.projection.len();
+        for (i, (base, elem)) in place.iter_projections().enumerate() {
+            let base_ty = base.ty(self.body, self.tcx).ty;
+            match elem {
+                ProjectionElem::Deref if base_ty.is_unsafe_ptr() => {
+                    self.record(UnsafeKind::RawPtrDeref, location);
+                }
+                // Assigning directly to a union field is safe; anything else reads it.
+                ProjectionElem::Field(..)
+                    if base_ty.is_union() && !(i + 1 == last && context.is_place_assignment()) =>
+                {
+                    self.record(UnsafeKind::UnionFieldAccess, location);
+                }
+                _ => {}
+            }
+        }
+
+        self.super_place(place, context, location);
+    }
+}
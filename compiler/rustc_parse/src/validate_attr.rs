@@ -79,6 +79,9 @@ fn is_attr_template_compatible(template: &AttributeTemplate, meta: &ast::MetaIte
         MetaItemKind::Word => template.word,
         MetaItemKind::List(..) => template.list.is_some(),
         MetaItemKind::NameValue(lit) if lit.kind.is_str() => template.name_value_str.is_some(),
+        MetaItemKind::NameValue(ast::Lit { kind: ast::LitKind::Int(..), .. }) => {
+            template.name_value_int.is_some()
+        }
         MetaItemKind::NameValue(..) => false,
     }
 }
@@ -129,10 +132,19 @@ pub fn check_builtin_attribute(
                     if !first {
                         msg.push_str(" or ");
                     }
+                    first = false;
                     let code = format!("#[{} = \"{}\"]", name, descr);
                     msg.push_str(&format!("`{}`", &code));
                     suggestions.push(code);
                 }
+                if let Some(descr) = template.name_value_int {
+                    if !first {
+                        msg.push_str(" or ");
+                    }
+                    let code = format!("#[{} = {}]", name, descr);
+                    msg.push_str(&format!("`{}`", &code));
+                    suggestions.push(code);
+                }
                 if should_warn(name) {
                     sess.buffer_lint(
                         &ILL_FORMED_ATTRIBUTE_INPUT,
@@ -6,7 +6,7 @@
 
 use rustc_middle::hir::map::Map;
 use rustc_middle::ty::query::Providers;
-use rustc_middle::ty::TyCtxt;
+use rustc_middle::ty::{ParamEnv, TyCtxt};
 
 use rustc_ast::{Attribute, Lit, LitKind, NestedMetaItem};
 use rustc_errors::{pluralize, struct_span_err, Applicability};
@@ -99,6 +99,10 @@ fn check_attributes(
                 self.check_naked(hir_id, attr, span, target)
             } else if self.tcx.sess.check_name(attr, sym::rustc_legacy_const_generics) {
                 self.check_rustc_legacy_const_generics(&attr, span, target, item)
+            } else if self.tcx.sess.check_name(attr, sym::yk_trace_budget) {
+                self.check_yk_trace_budget(attr, span, target)
+            } else if self.tcx.sess.check_name(attr, sym::yk_stable_address) {
+                self.check_yk_stable_address(hir_id, attr, span, target)
             } else if self.tcx.sess.check_name(attr, sym::rustc_clean)
                 || self.tcx.sess.check_name(attr, sym::rustc_dirty)
                 || self.tcx.sess.check_name(attr, sym::rustc_if_this_changed)
@@ -1030,6 +1034,64 @@ fn check_rustc_legacy_const_generics(
         }
     }
 
+    /// Checks if `#[yk_trace_budget]` is applied to a function definition or a closure.
+    fn check_yk_trace_budget(&self, attr: &Attribute, span: &Span, target: Target) -> bool {
+        match target {
+            Target::Fn
+            | Target::Closure
+            | Target::Method(MethodKind::Trait { body: true } | MethodKind::Inherent) => true,
+            _ => {
+                self.tcx
+                    .sess
+                    .struct_span_err(
+                        attr.span,
+                        "attribute should be applied to a function definition or closure",
+                    )
+                    .span_label(*span, "not a function definition or closure")
+                    .emit();
+                false
+            }
+        }
+    }
+
+    /// Checks if `#[yk_stable_address]` is applied to a static which is never written to. Such
+    /// statics are made read-only once relocated, so their contents must not change at runtime.
+    fn check_yk_stable_address(
+        &self,
+        hir_id: HirId,
+        attr: &Attribute,
+        span: &Span,
+        target: Target,
+    ) -> bool {
+        if target != Target::Static {
+            self.tcx
+                .sess
+                .struct_span_err(attr.span, "attribute should be applied to a static")
+                .span_label(*span, "not a static")
+                .emit();
+            return false;
+        }
+
+        let label = if let ItemKind::Static(_, hir::Mutability::Mut, _) =
+            self.tcx.hir().expect_item(hir_id).kind
+        {
+            "mutable static"
+        } else {
+            let ty = self.tcx.type_of(self.tcx.hir().local_def_id(hir_id));
+            if ty.is_freeze(self.tcx.at(*span), ParamEnv::empty()) {
+                return true;
+            }
+            "static with interior mutability"
+        };
+        self.tcx
+            .sess
+            .struct_span_err(attr.span, "attribute should be applied to an immutable static")
+            .span_label(*span, label)
+            .note("statics with a stable address are placed in a read-only section")
+            .emit();
+        false
+    }
+
     /// Checks that the dep-graph debugging attributes are only present when the query-dep-graph
     /// option is passed to the compiler.
     fn check_rustc_dirty_clean(&self, attr: &Attribute) -> bool {
@@ -1213,6 +1213,15 @@ fn parse_split_debuginfo(slot: &mut Option<SplitDebuginfo>, v: Option<&str>) ->
         "verify LLVM IR (default: no)"),
     wasi_exec_model: Option<WasiExecModel> = (None, parse_wasi_exec_model, [TRACKED],
         "whether to build a wasi command or reactor"),
+    yk_canonicalise: bool = (false, parse_bool, [TRACKED],
+        "put MIR into a canonical form before codegen, so that equivalent functions \
+        have equal `yk_mir_hash`es (default: no)"),
+    yk_hoist_invariant: bool = (false, parse_bool, [TRACKED],
+        "hoist loop-invariant computations into loop pre-headers (default: no)"),
+    yk_print_mir_hash: bool = (false, parse_bool, [UNTRACKED],
+        "print the `yk_mir_hash` of each function in the crate (default: no)"),
+    yk_split_critical_edges: bool = (false, parse_bool, [TRACKED],
+        "split critical edges in MIR before codegen, as SSA construction needs (default: no)"),
 
     // This list is in alphabetical order.
     //
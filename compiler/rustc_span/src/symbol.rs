@@ -1038,6 +1038,8 @@
         rustc_then_this_would_need,
         rustc_unsafe_specialization_marker,
         rustc_variance,
+        rustc_yk_side_effects,
+        rustc_yk_unsafe_regions,
         rustdoc,
         rustfmt,
         rvalue_static_promotion,
@@ -1311,6 +1313,9 @@
         wrapping_sub,
         write_bytes,
         xmm_reg,
+        yk,
+        yk_stable_address,
+        yk_trace_budget,
         ymm_reg,
         zmm_reg,
     }
@@ -2651,6 +2651,7 @@ fn codegen_fn_attrs(tcx: TyCtxt<'_>, id: DefId) -> CodegenFnAttrs {
     let mut inline_span = None;
     let mut link_ordinal_span = None;
     let mut no_sanitize_span = None;
+    let mut yk_stable_address_span = None;
     for attr in attrs.iter() {
         if tcx.sess.check_name(attr, sym::cold) {
             codegen_fn_attrs.flags |= CodegenFnAttrFlags::COLD;
@@ -2921,6 +2922,23 @@ fn codegen_fn_attrs(tcx: TyCtxt<'_>, id: DefId) -> CodegenFnAttrs {
                 },
                 None => None,
             };
+        } else if tcx.sess.check_name(attr, sym::yk_trace_budget) {
+            codegen_fn_attrs.yk_trace_budget = check_yk_trace_budget(tcx, attr);
+        } else if tcx.sess.check_name(attr, sym::yk_stable_address) {
+            yk_stable_address_span = Some(attr.span);
+        }
+    }
+
+    // Statics which traces may refer to by address are gathered into one section, so that the Yk
+    // runtime can find them and check that they haven't moved since a trace was compiled. Only
+    // immutable statics without interior mutability are accepted, so the section is read-only.
+    if let Some(span) = yk_stable_address_span {
+        if tcx.sess.target.is_like_osx {
+            tcx.sess.span_err(span, "`#[yk_stable_address]` is not supported on Mach-O targets");
+        } else if codegen_fn_attrs.link_section.is_some() {
+            tcx.sess.span_err(span, "cannot use `#[link_section]` with `#[yk_stable_address]`");
+        } else {
+            codegen_fn_attrs.link_section = Some(Symbol::intern(".yk_stable_globals"));
         }
     }
 
@@ -3117,6 +3135,25 @@ fn check_link_ordinal(tcx: TyCtxt<'_>, attr: &ast::Attribute) -> Option<usize> {
     }
 }
 
+fn check_yk_trace_budget(tcx: TyCtxt<'_>, attr: &ast::Attribute) -> Option<u32> {
+    // A malformed attribute has no integer value and has already been reported.
+    let meta = attr.meta()?;
+    let lit = meta.name_value_literal()?;
+    match lit.kind {
+        ast::LitKind::Int(budget, _) if budget > 0 && budget <= u128::from(u32::MAX) => {
+            Some(budget as u32)
+        }
+        ast::LitKind::Int(..) => {
+            tcx.sess
+                .struct_span_err(attr.span, "invalid `yk_trace_budget` attribute")
+                .span_label(lit.span, "expected a positive integer")
+                .emit();
+            None
+        }
+        _ => None,
+    }
+}
+
 fn check_link_name_xor_ordinal(
     tcx: TyCtxt<'_>,
     codegen_fn_attrs: &CodegenFnAttrs,
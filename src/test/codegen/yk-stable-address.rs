@@ -0,0 +1,10 @@
+// ignore-macos
+// compile-flags: -C no-prepopulate-passes
+
+#![crate_type = "lib"]
+#![feature(yk)]
+
+// CHECK: @STABLE = {{.*}}constant {{.*}}, section ".yk_stable_globals"
+#[no_mangle]
+#[yk_stable_address]
+pub static STABLE: u32 = 1;
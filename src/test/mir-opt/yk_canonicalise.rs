@@ -0,0 +1,16 @@
+// compile-flags: -Z yk-canonicalise
+// Check that storage markers are removed, locals renumbered in reverse post-order, `SwitchInt`
+// targets sorted, and places put before constants in commutative operations.
+
+// EMIT_MIR yk_canonicalise.canonical.YkMirCanonicaliser.diff
+pub fn canonical(x: u8, y: u32, z: u32) -> u32 {
+    match x {
+        9 => 3 + y,
+        0 => 1,
+        _ => z * 2,
+    }
+}
+
+fn main() {
+    canonical(9, 1, 2);
+}
@@ -0,0 +1,17 @@
+// compile-flags: -Z yk-hoist-invariant
+// Check that a loop-invariant multiplication and its operands are moved into the pre-header.
+
+// EMIT_MIR yk_hoist_invariant.scaled_sum.YkHoistInvariant.diff
+pub fn scaled_sum(a: u32, b: u32, n: u32) -> u32 {
+    let mut acc = 0;
+    let mut i = 0;
+    while i < n {
+        acc += a * b;
+        i += 1;
+    }
+    acc
+}
+
+fn main() {
+    scaled_sum(3, 4, 5);
+}
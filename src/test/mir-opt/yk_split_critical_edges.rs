@@ -0,0 +1,17 @@
+// compile-flags: -Z yk-split-critical-edges
+// Check that the edge from a `match` straight to the block where its arms join is split.
+
+// EMIT_MIR yk_split_critical_edges.join.YkSplitCriticalEdges.diff
+pub fn join(x: u8, y: u32) -> u32 {
+    let mut z = y;
+    match x {
+        0 => z = 1,
+        1 => {}
+        _ => z = 2,
+    }
+    z + 1
+}
+
+fn main() {
+    join(0, 1);
+}
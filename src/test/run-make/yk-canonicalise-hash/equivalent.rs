@@ -0,0 +1,26 @@
+// `first` and `second` compile to the same MIR, except that their locals are numbered differently
+// and one operand order is swapped. Storage markers, which would otherwise fix the numbering in
+// declaration order, are not emitted at `-C opt-level=0`.
+
+pub fn first(a: u32, b: u32) -> u32 {
+    let (x, y);
+    x = a ^ 1;
+    y = b & 2;
+    x | y
+}
+
+pub fn second(a: u32, b: u32) -> u32 {
+    let (y, x);
+    x = 1 ^ a;
+    y = b & 2;
+    x | y
+}
+
+// Compiled at different `-C opt-level`s, `classify` only differs in its storage markers.
+pub fn classify(x: u8, y: u32) -> u32 {
+    match x {
+        9 => y ^ 3,
+        0 => 1,
+        _ => y & 2,
+    }
+}
@@ -0,0 +1,15 @@
+// A comment moves the spans of everything below it, but not the MIR itself.
+
+pub fn scale(x: u32) -> u32 {
+    // So does one inside a function.
+    let y = x * 3;
+    y + 1
+}
+
+pub fn unchanged(x: u32) -> u32 {
+    if x > 10 { scale(x) } else { x }
+}
+
+pub fn table() -> &'static [u32] {
+    &[1, 2, 3]
+}
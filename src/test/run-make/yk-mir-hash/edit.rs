@@ -0,0 +1,12 @@
+pub fn scale(x: u32) -> u32 {
+    let y = x * 4;
+    y + 1
+}
+
+pub fn unchanged(x: u32) -> u32 {
+    if x > 10 { scale(x) } else { x }
+}
+
+pub fn table() -> &'static [u32] {
+    &[1, 2, 4]
+}
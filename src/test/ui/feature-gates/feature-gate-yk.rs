@@ -0,0 +1,5 @@
+#[yk_trace_budget = 500] //~ ERROR the `#[yk_trace_budget]` attribute is an experimental feature
+fn main() {}
+
+#[yk_stable_address] //~ ERROR the `#[yk_stable_address]` attribute is an experimental feature
+static STABLE: u32 = 0;
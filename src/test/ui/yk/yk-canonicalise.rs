@@ -0,0 +1,34 @@
+// Check that canonicalised MIR still validates and behaves the same.
+
+// run-pass
+// compile-flags: -Z yk-canonicalise -Z validate-mir
+
+fn classify(x: u8) -> u32 {
+    match x {
+        9 => 90,
+        3 => 30,
+        0 => 1,
+        _ => 2,
+    }
+}
+
+fn sum(n: u32) -> u32 {
+    let mut acc = 0;
+    let mut i = 0;
+    while i < n {
+        acc = 3 + acc;
+        acc = (i * 2) ^ acc;
+        i += 1;
+    }
+    acc
+}
+
+fn main() {
+    assert_eq!(classify(0), 1);
+    assert_eq!(classify(3), 30);
+    assert_eq!(classify(9), 90);
+    assert_eq!(classify(4), 2);
+    assert_eq!(sum(0), 0);
+    assert_eq!(sum(3), 3);
+    assert_eq!(sum(5), 11);
+}
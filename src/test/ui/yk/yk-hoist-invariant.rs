@@ -0,0 +1,64 @@
+// Check that hoisting loop invariants leaves valid MIR which computes the same results.
+
+// run-pass
+// compile-flags: -O -Z yk-hoist-invariant -Z validate-mir
+
+#[inline(never)]
+fn scaled_sum(a: u32, b: u32, n: u32) -> u32 {
+    let mut acc = 0;
+    let mut i = 0;
+    while i < n {
+        let x = a * b;
+        acc += x + i;
+        i += 1;
+    }
+    acc
+}
+
+#[inline(never)]
+fn nested(a: u32, n: u32) -> u32 {
+    let mut acc = 0;
+    for i in 0..n {
+        for j in 0..n {
+            let x = a ^ 3;
+            let y = x + i;
+            acc += y * j;
+        }
+    }
+    acc
+}
+
+#[inline(never)]
+fn zero_trip(a: u32, n: u32) -> u32 {
+    let mut acc = 7;
+    for _ in 0..n {
+        let x = a + 1;
+        acc = x;
+    }
+    acc
+}
+
+#[inline(never)]
+fn bump(mut a: [u64; 4]) -> u64 {
+    a[0] += 1;
+    a[0] + a[3]
+}
+
+// The copy of `arr` passed to `bump` is hoisted, so it must not be moved into the call: the callee
+// could then overwrite it in place, changing the argument of the next iteration's call.
+#[inline(never)]
+fn repeated(arr: [u64; 4], n: u32) -> u64 {
+    let mut s = 0;
+    for _ in 0..n {
+        s += bump(arr);
+    }
+    s
+}
+
+fn main() {
+    assert_eq!(scaled_sum(3, 4, 5), 12 * 5 + (0 + 1 + 2 + 3 + 4));
+    assert_eq!(nested(5, 3), (6 + 0) * 3 + (6 + 1) * 3 + (6 + 2) * 3);
+    assert_eq!(zero_trip(1, 0), 7);
+    assert_eq!(zero_trip(1, 2), 2);
+    assert_eq!(repeated([1, 2, 3, 4], 3), 18);
+}
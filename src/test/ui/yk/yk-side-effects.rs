@@ -0,0 +1,62 @@
+// Check how the `yk_trace_side_effects` query classifies functions. MIR inlining is enabled so that
+// the writes made by `ptr` functions end up in the bodies which call them.
+
+// build-fail
+// compile-flags: -Z inline-mir
+
+#![feature(rustc_attrs)]
+#![crate_type = "lib"]
+
+extern "C" {
+    // Foreign functions have no MIR to analyse.
+    #[rustc_yk_side_effects]
+    //~^ ERROR yk-side-effects(Unknown)
+    fn ext(x: u32) -> u32;
+}
+
+static mut COUNTER: u32 = 0;
+
+#[rustc_yk_side_effects]
+//~^ ERROR yk-side-effects(Pure)
+pub fn pure(x: u32) -> u32 {
+    x + 1
+}
+
+#[rustc_yk_side_effects]
+//~^ ERROR yk-side-effects(HasIO)
+pub fn extern_call(x: u32) -> u32 {
+    unsafe { ext(x) }
+}
+
+// Intrinsics are declared in `extern` blocks, but are not foreign code.
+#[rustc_yk_side_effects]
+//~^ ERROR yk-side-effects(Pure)
+pub fn intrinsic_call(x: u32) -> f32 {
+    unsafe { std::mem::transmute(x) }
+}
+
+#[rustc_yk_side_effects]
+//~^ ERROR yk-side-effects(HasGlobalMutation)
+pub fn static_write(x: u32) {
+    unsafe {
+        COUNTER = x;
+    }
+}
+
+#[rustc_yk_side_effects]
+//~^ ERROR yk-side-effects(HasGlobalMutation)
+pub fn raw_borrow(p: *mut u32) -> &'static mut u32 {
+    unsafe { &mut *p }
+}
+
+#[rustc_yk_side_effects]
+//~^ ERROR yk-side-effects(HasGlobalMutation)
+pub fn ptr_write(p: *mut u32, x: u32) {
+    unsafe { std::ptr::write(p, x) }
+}
+
+#[rustc_yk_side_effects]
+//~^ ERROR yk-side-effects(HasGlobalMutation)
+pub fn write_volatile(p: *mut u32, x: u32) {
+    unsafe { std::ptr::write_volatile(p, x) }
+}
@@ -0,0 +1,20 @@
+#![feature(yk)]
+
+use std::sync::atomic::AtomicU32;
+
+#[yk_stable_address]
+static STABLE: u32 = 0;
+
+#[yk_stable_address] //~ ERROR attribute should be applied to a static
+fn not_a_static() {}
+
+#[yk_stable_address] //~ ERROR attribute should be applied to a static
+const NOT_A_STATIC: u32 = 0;
+
+#[yk_stable_address] //~ ERROR attribute should be applied to an immutable static
+static mut MUTABLE: u32 = 0;
+
+#[yk_stable_address] //~ ERROR attribute should be applied to an immutable static
+static INTERIOR: AtomicU32 = AtomicU32::new(0);
+
+fn main() {}
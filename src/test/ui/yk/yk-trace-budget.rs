@@ -0,0 +1,18 @@
+#![feature(yk)]
+
+#[yk_trace_budget = 500]
+fn within_budget() {}
+
+#[yk_trace_budget = 0] //~ ERROR invalid `yk_trace_budget` attribute
+fn zero() {}
+
+#[yk_trace_budget = 5000000000] //~ ERROR invalid `yk_trace_budget` attribute
+fn too_large() {}
+
+#[yk_trace_budget = "500"] //~ ERROR malformed `yk_trace_budget` attribute input
+fn string() {}
+
+#[yk_trace_budget = 500] //~ ERROR attribute should be applied to a function definition or closure
+struct S;
+
+fn main() {}
@@ -0,0 +1,68 @@
+// Check which blocks the `yk_trace_unsafe_regions` query reports for each kind of unsafe
+// operation, and that blocks of the same kind which control flows straight through are merged into
+// one region.
+
+// build-fail
+
+#![feature(core_intrinsics, rustc_attrs)]
+#![crate_type = "lib"]
+
+pub union U {
+    pub a: u32,
+    pub b: f32,
+}
+
+extern "C" {
+    fn ext(x: u32) -> u32;
+}
+
+#[rustc_yk_unsafe_regions]
+//~^ ERROR yk-unsafe-regions(bb0: RawPtrDeref, bb0: UnsafeBlock)
+pub fn raw_deref(p: *const u32) -> u32 {
+    unsafe { *p }
+}
+
+#[rustc_yk_unsafe_regions]
+//~^ ERROR yk-unsafe-regions(bb0: UnionFieldAccess, bb0: UnsafeBlock)
+pub fn union_read(u: U) -> u32 {
+    unsafe { u.a }
+}
+
+// Assigning to a union field is safe, so nothing is reported.
+#[rustc_yk_unsafe_regions]
+//~^ ERROR yk-unsafe-regions()
+pub fn union_write(u: &mut U, x: u32) {
+    u.a = x;
+}
+
+#[rustc_yk_unsafe_regions]
+//~^ ERROR yk-unsafe-regions(bb0: ExternCall, bb0: UnsafeBlock)
+pub fn extern_call(x: u32) -> u32 {
+    unsafe { ext(x) }
+}
+
+#[rustc_yk_unsafe_regions]
+//~^ ERROR yk-unsafe-regions(bb0..=bb1: ExternCall, bb0..=bb1: UnsafeBlock)
+pub fn merged(x: u32) -> u32 {
+    unsafe { ext(ext(x)) }
+}
+
+// The calls are in different arms of the `if`, so they are not merged.
+#[rustc_yk_unsafe_regions]
+//~^ ERROR yk-unsafe-regions(bb1: ExternCall, bb1: UnsafeBlock, bb2: ExternCall, bb2: UnsafeBlock)
+pub fn branches(c: bool, x: u32) -> u32 {
+    if c { unsafe { ext(x) } } else { unsafe { ext(0) } }
+}
+
+// An inlined `ptr::read` or `ptr::write` is left as this copy, which dereferences both pointers.
+#[rustc_yk_unsafe_regions]
+//~^ ERROR yk-unsafe-regions(bb0: RawPtrDeref, bb0: UnsafeBlock)
+pub fn copy(p: *const u32, q: *mut u32) {
+    unsafe { std::ptr::copy_nonoverlapping(p, q, 1) }
+}
+
+#[rustc_yk_unsafe_regions]
+//~^ ERROR yk-unsafe-regions(bb0: RawPtrDeref, bb0: UnsafeBlock)
+pub fn volatile_store(p: *mut u32, x: u32) {
+    unsafe { std::intrinsics::volatile_store(p, x) }
+}